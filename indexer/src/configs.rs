@@ -1,5 +1,6 @@
 use aws_sdk_s3::Endpoint;
-use clap::{Parser, Subcommand};
+use cached::proc_macro::cached;
+use clap::{Parser, Subcommand, ValueEnum};
 use tracing_subscriber::EnvFilter;
 
 use explorer_database::{adapters, models};
@@ -23,12 +24,18 @@ pub(crate) struct Opts {
     /// Connection string to connect to the PostgreSQL Database to fetch AlertRules from
     #[clap(long, env)]
     pub database_url: String,
-    /// AWS Access Key with the rights to read from AWS S3
+    /// Object-store backend to source block data from. `aws` uses the default AWS credential
+    /// chain unless explicit keys are given; `gcs`/`azure`/`custom` target S3-compatible stores.
+    #[clap(long, env, value_enum, default_value_t = StorageBackend::Aws)]
+    pub storage_backend: StorageBackend,
+    /// AWS Access Key with the rights to read from AWS S3. Optional: when omitted the default AWS
+    /// credential chain (environment, profile, web-identity/IMDS) is used instead, whichever
+    /// backend is selected; GCS and Azure native credential chains are not consulted.
     #[clap(long, env)]
-    pub lake_aws_access_key: String,
-    /// AWS Secret Access Key with the rights to read from AWS S3
+    pub lake_aws_access_key: Option<String>,
+    /// AWS Secret Access Key with the rights to read from AWS S3. Optional, see `--lake-aws-access-key`.
     #[clap(long, env)]
-    pub lake_aws_secret_access_key: String,
+    pub lake_aws_secret_access_key: Option<String>,
     /// S3 endpoint in case you want to use custom solution like Minio or Localstack as a S3 compatible storage
     #[clap(long, env)]
     pub s3_endpoint: Option<http::Uri>,
@@ -38,6 +45,15 @@ pub(crate) struct Opts {
     /// S3 egion_name in case you want to use custom solution like Minio or Localstack as a S3 compatible storage
     #[clap(long, env)]
     pub s3_region_name: Option<String>,
+    /// Block data provider to stream from: `lake` (NEAR Lake on AWS S3) or `fastnear` (FastNEAR HTTP endpoint)
+    #[clap(long, env, value_enum, default_value_t = Provider::Lake)]
+    pub provider: Provider,
+    /// FastNEAR endpoint URL to fetch block data from when `--provider fastnear` is selected
+    #[clap(long, env)]
+    pub fastnear_endpoint: Option<String>,
+    /// Redis connection string used to persist and resume the `last_indexed_block` height
+    #[clap(long, env)]
+    pub redis_url: Option<String>,
     /// RPC url
     #[clap(long, env)]
     pub rpc_url: Option<String>,
@@ -55,6 +71,47 @@ pub(crate) struct Opts {
     pub chain_id: ChainId,
 }
 
+/// Object-store backend used to fetch NEAR Lake block data
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// Amazon S3 (default AWS credential chain unless explicit keys are provided)
+    Aws,
+    /// Google Cloud Storage via its S3-compatible interoperability endpoint
+    Gcs,
+    /// Azure Blob Storage via an S3-compatible gateway
+    Azure,
+    /// Any other S3-compatible store (e.g. MinIO) configured via `--s3-endpoint`
+    Custom,
+}
+
+impl StorageBackend {
+    /// Well-known S3-compatible endpoint for the backend, or `None` when there is none: AWS then
+    /// uses the SDK's default endpoint, while an Azure gateway or MinIO needs `--s3-endpoint`
+    fn default_endpoint(&self) -> Option<http::Uri> {
+        match self {
+            StorageBackend::Gcs => Some(http::Uri::from_static("https://storage.googleapis.com")),
+            StorageBackend::Aws | StorageBackend::Azure | StorageBackend::Custom => None,
+        }
+    }
+
+    /// Default region to sign requests for when `--s3-region-name` is not given
+    fn default_region(&self) -> &'static str {
+        match self {
+            StorageBackend::Gcs => "auto",
+            StorageBackend::Aws | StorageBackend::Azure | StorageBackend::Custom => "eu-central-1",
+        }
+    }
+}
+
+/// Block data provider the indexer streams from
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// NEAR Lake backed by an AWS S3 bucket (requires AWS credentials)
+    Lake,
+    /// FastNEAR HTTP/JSON endpoint (no AWS setup required)
+    FastNear,
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum ChainId {
     #[clap(subcommand)]
@@ -66,8 +123,14 @@ pub enum ChainId {
 #[allow(clippy::enum_variant_names)]
 #[derive(Subcommand, Debug, Clone)]
 pub enum StartOptions {
-    /// Start from specific block height
-    FromBlock { height: u64 },
+    /// Start from specific block height. Pass `--end-block` to backfill a bounded
+    /// `[height, end)` range and exit cleanly once it is reached.
+    FromBlock {
+        height: u64,
+        /// Exclusive upper bound for a bounded backfill. When omitted the indexer streams forever.
+        #[clap(long = "end-block")]
+        end: Option<u64>,
+    },
     /// Start from interruption (last_indexed_block value from Redis)
     FromInterruption,
     /// Start from the final block on the network (queries JSON RPC for finality: final)
@@ -82,33 +145,94 @@ impl Opts {
         }
     }
 
-    // Creates AWS Credentials for NEAR Lake
-    fn lake_credentials(&self) -> aws_types::credentials::SharedCredentialsProvider {
-        let provider = aws_types::Credentials::new(
-            self.lake_aws_access_key.clone(),
-            self.lake_aws_secret_access_key.clone(),
-            None,
-            None,
-            "alertexer_lake",
-        );
-        aws_types::credentials::SharedCredentialsProvider::new(provider)
+    /// Resolves credentials: explicit keys if given, otherwise the default AWS credential chain
+    /// (used for every backend)
+    async fn lake_credentials(&self) -> aws_types::credentials::SharedCredentialsProvider {
+        match (&self.lake_aws_access_key, &self.lake_aws_secret_access_key) {
+            (Some(access_key), Some(secret_access_key)) => {
+                let provider = aws_types::Credentials::new(
+                    access_key.clone(),
+                    secret_access_key.clone(),
+                    None,
+                    None,
+                    "alertexer_lake",
+                );
+                aws_types::credentials::SharedCredentialsProvider::new(provider)
+            }
+            _ => aws_types::credentials::SharedCredentialsProvider::new(
+                aws_config::default_provider::credentials::default_provider().await,
+            ),
+        }
     }
 
     /// Creates AWS Shared Config for NEAR Lake
-    pub fn lake_aws_sdk_config(&self) -> aws_types::sdk_config::SdkConfig {
+    pub async fn lake_aws_sdk_config(&self) -> aws_types::sdk_config::SdkConfig {
+        let region = self
+            .s3_region_name
+            .clone()
+            .unwrap_or_else(|| self.storage_backend.default_region().to_string());
         let mut s3_conf = aws_types::sdk_config::SdkConfig::builder()
-            .credentials_provider(self.lake_credentials())
-            .region(aws_types::region::Region::new("eu-central-1"));
+            .credentials_provider(self.lake_credentials().await)
+            .region(aws_types::region::Region::new(region));
 
-        // Owerride S3 endpoint in case you want to use custom solution
-        // like Minio or Localstack as a S3 compatible storage
-        if let Some(s3_endpoint) = &self.s3_endpoint {
-            s3_conf = s3_conf.endpoint_resolver(Endpoint::immutable(s3_endpoint.clone()));
+        // Pick the endpoint from the selected backend (GCS interop), letting an explicit
+        // `--s3-endpoint` override it for Azure gateways, MinIO or Localstack.
+        if let Some(endpoint) = self
+            .s3_endpoint
+            .clone()
+            .or_else(|| self.storage_backend.default_endpoint())
+        {
+            s3_conf = s3_conf.endpoint_resolver(Endpoint::immutable(endpoint));
         }
 
         s3_conf.build()
     }
 
+    /// Returns the network's `final` block height (TTL-cached, see [`final_block_height`])
+    pub async fn final_block_height(&self) -> anyhow::Result<u64> {
+        final_block_height(self).await
+    }
+
+    /// Opens a [`BlocksCache`] backed by Redis when `--redis-url` is provided
+    pub async fn redis_cache(&self) -> anyhow::Result<Option<BlocksCache>> {
+        match &self.redis_url {
+            Some(redis_url) => Ok(Some(BlocksCache::connect(redis_url).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Exclusive upper bound of the range to index, if a bounded backfill was requested
+    /// via `FromBlock { end: Some(_) }`. `None` means open-ended forward streaming.
+    pub fn end_block_height(&self) -> Option<u64> {
+        match self.start_options() {
+            StartOptions::FromBlock { end, .. } => *end,
+            _ => None,
+        }
+    }
+
+    /// Splits `[start, end)` into `concurrency` contiguous sub-ranges for parallel backfill
+    /// (fewer when the window holds fewer than `concurrency` heights)
+    pub fn backfill_ranges(&self, start: u64, end: u64) -> Vec<std::ops::Range<u64>> {
+        if end <= start {
+            return Vec::new();
+        }
+
+        let workers = u64::from(u16::from(self.concurrency)).min(end - start);
+        let span = end - start;
+        let chunk = span / workers;
+        let remainder = span % workers;
+
+        let mut ranges = Vec::with_capacity(workers as usize);
+        let mut cursor = start;
+        for worker in 0..workers {
+            // Spread the remainder across the first `remainder` workers so the window is covered exactly.
+            let len = chunk + if worker < remainder { 1 } else { 0 };
+            ranges.push(cursor..cursor + len);
+            cursor += len;
+        }
+        ranges
+    }
+
     pub fn rpc_url(&self) -> &str {
         if let Some(rpc_url) = &self.rpc_url {
             return rpc_url;
@@ -122,11 +246,39 @@ impl Opts {
 }
 
 impl Opts {
-    pub async fn to_lake_config(&self) -> near_lake_framework::LakeConfig {
-        let s3_config = aws_sdk_s3::config::Builder::from(&self.lake_aws_sdk_config()).build();
+    // `NearLakeFrameworkConfig::{Lake, FastNear}` and the `FastNearConfig`/`FastNearConfigBuilder`
+    // types used below require `near-lake-framework` >= 0.8 (the release that added the provider
+    // enum and made `streamer` accept it). Earlier versions only expose `LakeConfig`; keep the
+    // Cargo dependency pinned at or above that minor when bumping.
+    pub async fn to_lake_config(
+        &self,
+    ) -> anyhow::Result<near_lake_framework::NearLakeFrameworkConfig> {
+        let start_block_height = get_start_block_height(self).await?;
+        Ok(self.lake_config_from(start_block_height).await)
+    }
+
+    /// Builds a provider config that begins streaming at an explicit `start_block_height`,
+    /// letting backfill workers stream their own sub-range of the window
+    pub async fn lake_config_from(
+        &self,
+        start_block_height: u64,
+    ) -> near_lake_framework::NearLakeFrameworkConfig {
+        match self.provider {
+            Provider::Lake => near_lake_framework::NearLakeFrameworkConfig::Lake(
+                self.to_s3_lake_config(start_block_height).await,
+            ),
+            Provider::FastNear => near_lake_framework::NearLakeFrameworkConfig::FastNear(
+                self.to_fastnear_config(start_block_height),
+            ),
+        }
+    }
+
+    /// Builds the S3-backed NEAR Lake config (the default provider)
+    async fn to_s3_lake_config(&self, start_block_height: u64) -> near_lake_framework::LakeConfig {
+        let s3_config =
+            aws_sdk_s3::config::Builder::from(&self.lake_aws_sdk_config().await).build();
         let mut config_builder =
             near_lake_framework::LakeConfigBuilder::default().s3_config(s3_config);
-        let start_block_height = get_start_block_height(self).await;
 
         config_builder = match &self.chain_id {
             ChainId::Mainnet(_) => config_builder
@@ -147,19 +299,63 @@ impl Opts {
 
         config_builder.build().expect("Failed to build LakeConfig")
     }
+
+    /// Builds the FastNEAR HTTP config, fetching blocks over plain HTTP/JSON
+    /// instead of paginating S3 objects (no AWS credentials required)
+    fn to_fastnear_config(&self, start_block_height: u64) -> near_lake_framework::FastNearConfig {
+        let mut config_builder = near_lake_framework::FastNearConfigBuilder::default();
+
+        config_builder = match &self.chain_id {
+            ChainId::Mainnet(_) => config_builder
+                .mainnet()
+                .start_block_height(start_block_height),
+            ChainId::Testnet(_) => config_builder
+                .testnet()
+                .start_block_height(start_block_height),
+        };
+
+        if let Some(fastnear_endpoint) = &self.fastnear_endpoint {
+            config_builder = config_builder.endpoint(fastnear_endpoint);
+        }
+
+        config_builder
+            .build()
+            .expect("Failed to build FastNearConfig")
+    }
 }
 
-async fn get_start_block_height(opts: &Opts) -> u64 {
-    match opts.start_options() {
-        StartOptions::FromBlock { height } => *height,
+async fn get_start_block_height(opts: &Opts) -> anyhow::Result<u64> {
+    Ok(match opts.start_options() {
+        StartOptions::FromBlock { height, .. } => *height,
         StartOptions::FromInterruption => {
+            // Prefer the `last_indexed_block` value persisted in Redis (as the help text promises),
+            // falling back to the PostgreSQL-tracked height and finally to the network's final block.
+            if let Some(mut cache) = opts.redis_cache().await.unwrap_or_else(|err| {
+                tracing::warn!(
+                    target: "alertexer",
+                    "Failed to connect to Redis. Falling back to the Database...\n{:#?}",
+                    err
+                );
+                None
+            }) {
+                match cache.get_last_indexed_block().await {
+                    Ok(Some(last_indexed_block)) => return Ok(last_indexed_block),
+                    Ok(None) => {}
+                    Err(err) => tracing::warn!(
+                        target: "alertexer",
+                        "Failed to get last indexed block from Redis. Falling back to the Database...\n{:#?}",
+                        err
+                    ),
+                }
+            }
+
             let pool = models::establish_connection(&opts.database_url);
             let last_indexed_block: u64 = match adapters::blocks::latest_block_height(&pool).await {
                 Ok(last_indexed_block) => {
                     if let Some(last_indexed_block) = last_indexed_block {
                         last_indexed_block
                     } else {
-                        final_block_height(opts).await
+                        final_block_height(opts).await?
                     }
                 }
                 Err(err) => {
@@ -168,12 +364,59 @@ async fn get_start_block_height(opts: &Opts) -> u64 {
                         "Failed to get last indexer block from Database. Failing to the latest one...\n{:#?}",
                         err
                     );
-                    final_block_height(opts).await
+                    final_block_height(opts).await?
                 }
             };
             last_indexed_block
         }
-        StartOptions::FromLatest => final_block_height(opts).await,
+        StartOptions::FromLatest => final_block_height(opts).await?,
+    })
+}
+
+/// Caches the indexer's progress in Redis so a restart can resume from `last_indexed_block`
+#[derive(Clone)]
+pub struct BlocksCache {
+    connection: redis::aio::ConnectionManager,
+}
+
+impl BlocksCache {
+    const LAST_INDEXED_BLOCK_KEY: &'static str = "last_indexed_block";
+    const FINAL_BLOCK_HEIGHT_KEY: &'static str = "final_block_height";
+
+    /// Opens a connection manager to the Redis instance at `redis_url`
+    pub async fn connect(redis_url: &str) -> anyhow::Result<Self> {
+        let client = redis::Client::open(redis_url)?;
+        let connection = client.get_tokio_connection_manager().await?;
+        Ok(Self { connection })
+    }
+
+    /// Reads the persisted `last_indexed_block` height, if any
+    pub async fn get_last_indexed_block(&mut self) -> anyhow::Result<Option<u64>> {
+        let height: Option<u64> = redis::cmd("GET")
+            .arg(Self::LAST_INDEXED_BLOCK_KEY)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(height)
+    }
+
+    /// Persists the height of the block that has just been processed
+    pub async fn set_last_indexed_block(&mut self, height: u64) -> anyhow::Result<()> {
+        redis::cmd("SET")
+            .arg(Self::LAST_INDEXED_BLOCK_KEY)
+            .arg(height)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+
+    /// Stores the network's most recent `final` block height for downstream consumers
+    pub async fn set_final_block_height(&mut self, height: u64) -> anyhow::Result<()> {
+        redis::cmd("SET")
+            .arg(Self::FINAL_BLOCK_HEIGHT_KEY)
+            .arg(height)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(())
     }
 }
 
@@ -209,13 +452,56 @@ pub(crate) fn init_tracing(debug: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn final_block_height(opts: &Opts) -> u64 {
-    let client = JsonRpcClient::connect(opts.rpc_url());
+/// Returns the network's `final` block height, memoized with a ~1s TTL keyed by chain id
+async fn final_block_height(opts: &Opts) -> anyhow::Result<u64> {
+    let chain_id = match opts.chain_id {
+        ChainId::Mainnet(_) => "mainnet",
+        ChainId::Testnet(_) => "testnet",
+    };
+    cached_final_block_height(chain_id.to_string(), opts.rpc_url().to_string()).await
+}
+
+#[cached(
+    time = 1,
+    key = "String",
+    convert = r#"{ chain_id.clone() }"#,
+    result = true
+)]
+async fn cached_final_block_height(chain_id: String, rpc_url: String) -> anyhow::Result<u64> {
+    const MAX_ATTEMPTS: usize = 5;
+
+    let client = JsonRpcClient::connect(&rpc_url);
     let request = methods::block::RpcBlockRequest {
         block_reference: BlockReference::Finality(Finality::Final),
     };
 
-    let latest_block = client.call(request).await.unwrap();
-
-    latest_block.header.height
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match client.call(&request).await {
+            Ok(latest_block) => return Ok(latest_block.header.height),
+            Err(err) if attempt < MAX_ATTEMPTS => {
+                // Exponential backoff before retrying a transient RPC failure
+                let backoff = std::time::Duration::from_millis(100 * 2u64.pow(attempt as u32 - 1));
+                tracing::warn!(
+                    target: "alertexer",
+                    "Failed to fetch final block height for {} (attempt {}/{}), retrying in {:?}...\n{:#?}",
+                    chain_id,
+                    attempt,
+                    MAX_ATTEMPTS,
+                    backoff,
+                    err
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            Err(err) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to fetch final block height for {} after {} attempts: {:#?}",
+                    chain_id,
+                    MAX_ATTEMPTS,
+                    err
+                ))
+            }
+        }
+    }
 }
@@ -0,0 +1,167 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use clap::Parser;
+use futures::StreamExt;
+use tokio::sync::Mutex;
+
+use explorer_database::{adapters, models};
+
+use crate::configs::{BlocksCache, Opts, StartOptions};
+
+mod configs;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts: Opts = Opts::parse();
+    configs::init_tracing(opts.debug)?;
+
+    let pool = models::establish_connection(&opts.database_url);
+    let strict_mode = !opts.non_strict_mode;
+    let concurrency = usize::from(u16::from(opts.concurrency));
+
+    match (opts.end_block_height(), opts.start_options()) {
+        (Some(end), StartOptions::FromBlock { height, .. }) => {
+            run_backfill(&opts, &pool, strict_mode, *height, end).await?;
+        }
+        _ => run_stream(&opts, &pool, strict_mode, concurrency).await?,
+    }
+
+    Ok(())
+}
+
+/// Stores a single streamer message and returns the height of the processed block.
+///
+/// Takes the message by value so the returned future owns it and can be returned from a
+/// `.map(|streamer_message| …)` closure without borrowing the closure's parameter.
+async fn handle_streamer_message(
+    pool: &models::ConnectionPool,
+    streamer_message: near_lake_framework::near_indexer_primitives::StreamerMessage,
+    strict_mode: bool,
+) -> anyhow::Result<u64> {
+    let height = streamer_message.block.header.height;
+    adapters::store_streamer_message(pool, &streamer_message, strict_mode).await?;
+    Ok(height)
+}
+
+/// Open-ended forward streaming, persisting the tip to Redis after each block
+async fn run_stream(
+    opts: &Opts,
+    pool: &models::ConnectionPool,
+    strict_mode: bool,
+    concurrency: usize,
+) -> anyhow::Result<()> {
+    let mut cache = opts.redis_cache().await?;
+
+    let config = opts.to_lake_config().await?;
+    let (lake_handle, stream) = near_lake_framework::streamer(config);
+
+    let mut handlers = tokio_stream::wrappers::ReceiverStream::new(stream)
+        .map(|streamer_message| handle_streamer_message(pool, streamer_message, strict_mode))
+        .buffer_unordered(concurrency);
+
+    // Track the highest processed height so out-of-order completions (concurrency 2+) never
+    // rewind the persisted tip.
+    let mut last_indexed = 0;
+    while let Some(handled) = handlers.next().await {
+        let height = handled?;
+        if height > last_indexed {
+            last_indexed = height;
+            if let Some(cache) = cache.as_mut() {
+                cache.set_last_indexed_block(height).await?;
+                // Publish the network's final tip (TTL-cached) so consumers don't mistake the
+                // just-indexed height for the chain head.
+                cache.set_final_block_height(opts.final_block_height().await?).await?;
+            }
+        }
+    }
+    drop(handlers);
+
+    lake_handle.await??;
+    Ok(())
+}
+
+/// Backfills the bounded `[start, end)` window: splits it across `--concurrency` lake streams,
+/// runs them in parallel, joins them, and exits once the window is fully indexed.
+///
+/// Strict mode requires a transaction to be indexed before its child receipts, which disjoint
+/// parallel ranges cannot guarantee (a receipt in a later range may be processed before its
+/// parent transaction in an earlier one). So when strict mode is on the window is indexed by a
+/// single sequential stream instead of being split.
+async fn run_backfill(
+    opts: &Opts,
+    pool: &models::ConnectionPool,
+    strict_mode: bool,
+    start: u64,
+    end: u64,
+) -> anyhow::Result<()> {
+    let ranges = if strict_mode {
+        vec![start..end]
+    } else {
+        opts.backfill_ranges(start, end)
+    };
+
+    let cache = opts.redis_cache().await?.map(|c| Arc::new(Mutex::new(c)));
+    // Shared highest height persisted as `last_indexed_block`, so an interrupted backfill can be
+    // resumed via `FromInterruption`. With parallel disjoint ranges the tip is only the maximum
+    // height seen, not necessarily gap-free: a resumed run starts at that height, so blocks below
+    // it that a slower worker had not reached yet are not indexed by the resumed run.
+    let last_indexed = Arc::new(AtomicU64::new(0));
+
+    let mut workers = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        let config = opts.lake_config_from(range.start).await;
+        let pool = pool.clone();
+        let cache = cache.clone();
+        let last_indexed = Arc::clone(&last_indexed);
+        workers.push(tokio::spawn(async move {
+            stream_range(&pool, config, range, strict_mode, cache, last_indexed).await
+        }));
+    }
+
+    for worker in workers {
+        worker.await??;
+    }
+
+    tracing::info!(
+        target: "indexer_for_explorer",
+        "finished backfill of [{}, {}) ({} blocks)",
+        start,
+        end,
+        end - start,
+    );
+    Ok(())
+}
+
+/// Streams a single sub-range sequentially and stops at its exclusive `range.end`.
+///
+/// The stop is keyed on the first block whose height reaches `range.end` (NEAR skips heights, so
+/// `range.end - 1` may not exist) — that block belongs to the next worker and is left unindexed,
+/// keeping the ranges disjoint.
+async fn stream_range(
+    pool: &models::ConnectionPool,
+    config: near_lake_framework::NearLakeFrameworkConfig,
+    range: std::ops::Range<u64>,
+    strict_mode: bool,
+    cache: Option<Arc<Mutex<BlocksCache>>>,
+    last_indexed: Arc<AtomicU64>,
+) -> anyhow::Result<()> {
+    let (lake_handle, stream) = near_lake_framework::streamer(config);
+    let mut stream = tokio_stream::wrappers::ReceiverStream::new(stream);
+
+    while let Some(streamer_message) = stream.next().await {
+        if streamer_message.block.header.height >= range.end {
+            break;
+        }
+        let height = handle_streamer_message(pool, streamer_message, strict_mode).await?;
+        if let Some(cache) = &cache {
+            // Keep the persisted tip monotonic across workers.
+            if height > last_indexed.fetch_max(height, Ordering::SeqCst) {
+                cache.lock().await.set_last_indexed_block(height).await?;
+            }
+        }
+    }
+    lake_handle.abort();
+
+    Ok(())
+}